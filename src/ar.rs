@@ -0,0 +1,46 @@
+#![cfg(feature = "ar")]
+
+use std::{
+    fs,
+    io,
+    path::Path,
+};
+
+/// Extract a `.ar` (Unix static-lib) archive to `target`. `.ar` archives are flat,
+/// so every entry is written directly into `target` rather than into subdirectories.
+pub(crate) fn extract_ar<T: AsRef<Path> + ?Sized>(
+    archive: &T,
+    target: &Path
+) -> Result<(), crate::ExtractError> {
+    let file = fs::File::open(archive)?;
+    let mut archive = ::ar::Archive::new(file);
+    fs::create_dir_all(target)?;
+    while let Some(entry) = archive.next_entry() {
+        let mut entry = entry.map_err(crate::ExtractError::ArError)?;
+        let name = String::from_utf8_lossy(entry.header().identifier()).into_owned();
+        crate::reject_unsafe_path(&name)?;
+        let mut writer = fs::File::create(target.join(name))?;
+        io::copy(&mut entry, &mut writer).map_err(crate::ExtractError::ArError)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod ar_tests {
+    #[test]
+    fn unar() {
+        use crate::tests::dir_list_equals;
+        use crate::*;
+        let cur_file = Path::new(file!());
+        let root = cur_file.parent().unwrap().parent().unwrap();
+        let out = root.join("target").join("test").join("unar");
+        let _ = fs::remove_dir_all(out.as_path());
+        let data_dir = root.join("test_input");
+        assert_eq!(extract_archive(data_dir.join("file.ar").as_path(), Some(out.as_path())).unwrap(), out);
+        assert_eq!(true, dir_list_equals(out.as_path(), vec![ "compressed.txt" ]));
+        assert_eq!(
+            fs::read_to_string(out.join("compressed.txt")).unwrap(),
+            "ar works too".to_string()
+        );
+    }
+}
@@ -17,18 +17,45 @@ mod web;
 #[cfg(feature = "web")]
 pub use web::*;
 
-#[cfg(feature = "tgz")]
+#[cfg(any(feature = "tar", feature = "tgz"))]
 mod tgz;
 
 #[cfg(feature = "zip")]
 mod zip;
 
+#[cfg(feature = "txz")]
+mod txz;
+
+#[cfg(feature = "tzstd")]
+mod tzstd;
+
+#[cfg(feature = "tbz2")]
+mod tbz2;
+
+#[cfg(feature = "ar")]
+mod ar;
+
 /// A type specifying an error that occured during an archive extraction
 #[derive(Debug)]
 pub enum ExtractError {
     /// Failed to read the zip file
     #[cfg(feature = "zip")]
     ZipError(rc_zip::Error),
+    /// Failed to decompress a gzip-compressed file or to write out its contents
+    #[cfg(feature = "tgz")]
+    GzError(io::Error),
+    /// Failed to decompress an xz-compressed file or to write out its contents
+    #[cfg(feature = "txz")]
+    XzError(io::Error),
+    /// Failed to decompress a zstd-compressed file or to write out its contents
+    #[cfg(feature = "tzstd")]
+    ZstdError(io::Error),
+    /// Failed to decompress a bzip2-compressed file or to write out its contents
+    #[cfg(feature = "tbz2")]
+    Bz2Error(io::Error),
+    /// Failed to read the ar archive or to copy an entry's contents out of it
+    #[cfg(feature = "ar")]
+    ArError(io::Error),
     /// Failed to save files from the archive to the HDD
     WriteError(io::Error),
 }
@@ -46,6 +73,16 @@ impl fmt::Display for ExtractError {
         match *self {
             #[cfg(feature = "zip")]
             ZipError(ref e) => e.fmt(f),
+            #[cfg(feature = "tgz")]
+            GzError(ref e) => e.fmt(f),
+            #[cfg(feature = "txz")]
+            XzError(ref e) => e.fmt(f),
+            #[cfg(feature = "tzstd")]
+            ZstdError(ref e) => e.fmt(f),
+            #[cfg(feature = "tbz2")]
+            Bz2Error(ref e) => e.fmt(f),
+            #[cfg(feature = "ar")]
+            ArError(ref e) => e.fmt(f),
             WriteError(ref e) => e.fmt(f),
         }
     }
@@ -57,14 +94,45 @@ impl error::Error for ExtractError {
         match *self {
             #[cfg(feature = "zip")]
             ZipError(ref e) => Some(e),
+            #[cfg(feature = "tgz")]
+            GzError(ref e) => Some(e),
+            #[cfg(feature = "txz")]
+            XzError(ref e) => Some(e),
+            #[cfg(feature = "tzstd")]
+            ZstdError(ref e) => Some(e),
+            #[cfg(feature = "tbz2")]
+            Bz2Error(ref e) => Some(e),
+            #[cfg(feature = "ar")]
+            ArError(ref e) => Some(e),
             WriteError(ref e) => Some(e),
         }
     }
 }
 
-/// Extract the archive to a folder and return the path to the extracted files. Zip/tar/tar.gz archives are supported.
+/// Reject an archive-entry-supplied relative path (an entry name, or a symlink's link target)
+/// that could escape the extraction target: an absolute path, or one containing a `..`
+/// component.
+#[cfg(any(feature = "zip", feature = "ar"))]
+pub(crate) fn reject_unsafe_path(name: &str) -> io::Result<()> {
+    use std::path::Component;
+    if Path::new(name)
+        .components()
+        .any(|c| matches!(c, Component::ParentDir | Component::RootDir | Component::Prefix(_)))
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("archive entry path escapes the extraction target: {}", name),
+        ));
+    }
+    Ok(())
+}
+
+/// Extract the archive to a folder and return the path to the extracted files.
+/// Zip, tar, tar.gz/tgz, tar.xz, tar.zst/tar.zstd, tar.bz2, ar, and plain
+/// gz/xz/zst/bz2 single-file compressions are supported (depending on which
+/// features are enabled).
 /// If extraction target path isn't provided and the function is executed from a build script, the build script output folder is used.
-/// 
+///
 /// ```
 /// # fn run() -> Result<(), Box<dyn std::error::Error>> {
 /// # let path_to_lib_zip = std::path::Path::new("./whatever.zip");
@@ -98,6 +166,27 @@ pub fn extract_archive<T: AsRef<Path> + ?Sized>(
             return Ok(target);
         }
     }
+    #[cfg(feature = "txz")]
+    {
+        if fn_as_str.ends_with(".tar.xz") {
+            crate::txz::extract_tar_xz(archive, target.as_path())?;
+            return Ok(target);
+        }
+    }
+    #[cfg(feature = "tzstd")]
+    {
+        if fn_as_str.ends_with(".tar.zst") || fn_as_str.ends_with(".tar.zstd") {
+            crate::tzstd::extract_tar_zst(archive, target.as_path())?;
+            return Ok(target);
+        }
+    }
+    #[cfg(feature = "tbz2")]
+    {
+        if fn_as_str.ends_with(".tar.bz2") {
+            crate::tbz2::extract_tar_bz2(archive, target.as_path())?;
+            return Ok(target);
+        }
+    }
     #[cfg(feature = "tar")]
     {
         if fn_as_str.ends_with(".tar") {
@@ -105,7 +194,42 @@ pub fn extract_archive<T: AsRef<Path> + ?Sized>(
             return Ok(target);
         }
     }
-    
+    #[cfg(feature = "ar")]
+    {
+        if fn_as_str.ends_with(".ar") {
+            crate::ar::extract_ar(archive, target.as_path())?;
+            return Ok(target);
+        }
+    }
+    #[cfg(feature = "tgz")]
+    {
+        if fn_as_str.ends_with(".gz") {
+            crate::tgz::extract_gz(archive, target.as_path())?;
+            return Ok(target);
+        }
+    }
+    #[cfg(feature = "txz")]
+    {
+        if fn_as_str.ends_with(".xz") {
+            crate::txz::extract_xz(archive, target.as_path())?;
+            return Ok(target);
+        }
+    }
+    #[cfg(feature = "tzstd")]
+    {
+        if fn_as_str.ends_with(".zst") || fn_as_str.ends_with(".zstd") {
+            crate::tzstd::extract_zst(archive, target.as_path())?;
+            return Ok(target);
+        }
+    }
+    #[cfg(feature = "tbz2")]
+    {
+        if fn_as_str.ends_with(".bz2") {
+            crate::tbz2::extract_bz2(archive, target.as_path())?;
+            return Ok(target);
+        }
+    }
+
     panic!("archive format not supported");
 }
 
@@ -116,16 +240,47 @@ fn get_target_dir() -> io::Result<PathBuf>  {
     Ok(cur_exe.parent().unwrap().parent().unwrap().parent().unwrap().to_path_buf())
 }
 
-/// Get dynamic lib extension (.dll for windows targets, .so otherwise). Only works from build scripts.
+/// Get dynamic lib extension for the current build target: `dll` on Windows, `dylib` on Apple
+/// platforms, `so` everywhere else. Only works from build scripts.
 fn get_dylib_extension() -> Result<&'static str, env::VarError> {
     let target = env::var("TARGET")?;
     Ok(if target.contains("pc-windows") {
         "dll"
+    } else if target.contains("apple") {
+        "dylib"
     } else {
         "so"
     })
 }
 
+/// Get the dynamic lib filename prefix for the current build target: no prefix on Windows
+/// (`SDL2.dll`), `lib` everywhere else, including Apple platforms (`libSDL2.dylib`, `libSDL2.so`).
+/// Only works from build scripts.
+fn get_dylib_prefix() -> Result<&'static str, env::VarError> {
+    let target = env::var("TARGET")?;
+    Ok(if target.contains("pc-windows") {
+        ""
+    } else {
+        "lib"
+    })
+}
+
+/// Substitute `{target}`, `{os}`, `{arch}`, and `{dll_ext}` placeholders in `template` with the
+/// values for the current build target, read from the `TARGET`/`CARGO_CFG_TARGET_OS`/
+/// `CARGO_CFG_TARGET_ARCH` environment variables and [get_dylib_extension]. Only works from build
+/// scripts.
+pub(crate) fn expand_target_template(template: &str) -> Result<String, env::VarError> {
+    let target = env::var("TARGET")?;
+    let os = env::var("CARGO_CFG_TARGET_OS")?;
+    let arch = env::var("CARGO_CFG_TARGET_ARCH")?;
+    let dll_ext = get_dylib_extension()?;
+    Ok(template
+        .replace("{target}", &target)
+        .replace("{os}", &os)
+        .replace("{arch}", &arch)
+        .replace("{dll_ext}", dll_ext))
+}
+
 /// Dynamic library filter used to specify which library files needs to be copied.
 #[derive(Debug)]
 pub enum DyLibNameFilter<'a> {
@@ -134,11 +289,35 @@ pub enum DyLibNameFilter<'a> {
     /// Extension must match the string (Example: `"dll"`)
     Extension(&'a str),
     /// Library name must match the string (Example: `"SDL2"`).
-    /// Extension will be inferred from the target platform.
-    /// Files with an additional "lib" prefix will match as well.
+    /// Extension and filename prefix will be inferred from the target platform
+    /// (`SDL2.dll` on Windows, `libSDL2.dylib` on Apple platforms, `libSDL2.so` elsewhere),
+    /// and a trailing soname version (`libSDL2.so.2.0.0`) will match too.
     LibName(&'a str),
 }
 
+/// Does `file_name` look like the dynamic library named `lib_name` on the current platform?
+/// Tries the bare name (`SDL2.dll`) and the platform-prefixed name (`libSDL2.so`), and for either
+/// also accepts a trailing soname version, since Unix shared libs are commonly installed as
+/// `libfoo.so.1.2.3` rather than the bare `libfoo.so`.
+fn matches_lib_name(file_name: &str, lib_name: &str, prefix: &str, dotted_extension: &str) -> bool {
+    let candidates = [
+        lib_name.to_string() + dotted_extension,
+        prefix.to_string() + lib_name + dotted_extension,
+    ];
+    candidates.iter().any(|candidate| {
+        file_name == candidate
+            || file_name
+                .strip_prefix(candidate.as_str())
+                .map(is_version_suffix)
+                .unwrap_or(false)
+    })
+}
+
+/// Is `s` a run of one or more `.<digits>` components, e.g. `.1.2.3`?
+fn is_version_suffix(s: &str) -> bool {
+    s.starts_with('.') && s[1..].split('.').all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()))
+}
+
 /// Install all dynamic libs from a directory to the target directory.
 /// 
 /// The `dylib` argument can be used to specify the criteria a file needs to match to be installed (See [DyLibNameFilter](DyLibNameFilter) docs).
@@ -185,7 +364,9 @@ pub fn install_dylibs<T: AsRef<Path> + ?Sized>(
                 } else if let Some(Extension(_)) = filter {
                     file_name.ends_with(extension.as_str())
                 } else if let Some(LibName(lib_name)) = filter {
-                    file_name == lib_name.to_string() + &extension || file_name == "lib".to_string() + lib_name + &extension
+                    let prefix = get_dylib_prefix().expect("Couldn't detect dylib prefix");
+                    let dotted_extension = ".".to_string() + get_dylib_extension().expect("Couldn't detect dylib extension");
+                    matches_lib_name(file_name, lib_name, prefix, &dotted_extension)
                 } else {
                     file_name.ends_with(extension.as_str())
                 };
@@ -199,6 +380,30 @@ pub fn install_dylibs<T: AsRef<Path> + ?Sized>(
     Ok(())
 }
 
+/// Like [install_dylibs], but reads from `from.join(subdir_template)` instead of `from` directly,
+/// expanding the same `{target}`/`{os}`/`{arch}`/`{dll_ext}` placeholders as
+/// [download_for_target](crate::download_for_target). This lets a single archive ship binaries
+/// for several platforms side by side (e.g. under `lib/{target}/`) and only install the ones
+/// matching the current build target. Only works from build scripts.
+///
+/// ```
+/// # fn run() -> std::io::Result<()> {
+/// # let path_to_dylib_folder = std::path::Path::new(".");
+/// librarian::install_dylibs_for_target(path_to_dylib_folder, "lib/{target}", None, None)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn install_dylibs_for_target<T: AsRef<Path> + ?Sized>(
+    from: &T,
+    subdir_template: &str,
+    filter: Option<DyLibNameFilter<'_>>,
+    target_dir: Option<&Path>,
+) -> io::Result<()> {
+    let subdir = expand_target_template(subdir_template)
+        .expect("Couldn't resolve target placeholders (TARGET/CARGO_CFG_TARGET_OS/CARGO_CFG_TARGET_ARCH); are you running from a build script?");
+    install_dylibs(from.as_ref().join(subdir).as_path(), filter, target_dir)
+}
+
 /// Add a cargo link search path (only works strictly from a build script)
 /// 
 /// The function can be considered an analog of `install_dylibs` for static libs; it makes the static libs in a folder available to the linker.
@@ -221,9 +426,15 @@ mod tests {
         collections::HashMap,
         fs,
         path::Path,
+        sync::Mutex,
         vec::Vec,
     };
 
+    /// `cargo test` runs tests on multiple threads by default, but `TARGET`/
+    /// `CARGO_CFG_TARGET_OS`/`CARGO_CFG_TARGET_ARCH` are process-global, so any test that sets
+    /// them with `std::env::set_var` must hold this lock first to avoid racing a concurrent test.
+    pub(crate) static TARGET_ENV_LOCK: Mutex<()> = Mutex::new(());
+
     pub(crate) fn dir_list_equals(path: &Path, list: Vec<&'static str>) -> bool {
         let mut results = HashMap::new();
         for entry in fs::read_dir(path).unwrap() {
@@ -259,4 +470,65 @@ mod tests {
         assert_eq!(true, dir_list_equals(so_out.as_path(), vec![ "dummy.so", "libdummy.so" ]));
         assert_eq!(true, dir_list_equals(fn_out.as_path(), vec![ "dummy" ]));
     }
+
+    #[test]
+    fn matches_lib_name_test() {
+        use crate::matches_lib_name;
+        // Windows: no prefix, bare extension.
+        assert_eq!(matches_lib_name("SDL2.dll", "SDL2", "", ".dll"), true);
+        // Apple: `lib` prefix, `.dylib` extension.
+        assert_eq!(matches_lib_name("libSDL2.dylib", "SDL2", "lib", ".dylib"), true);
+        // Everywhere else: `lib` prefix, `.so` extension, with or without a soname version.
+        assert_eq!(matches_lib_name("libSDL2.so", "SDL2", "lib", ".so"), true);
+        assert_eq!(matches_lib_name("libSDL2.so.1.2.3", "SDL2", "lib", ".so"), true);
+
+        // Negative cases: wrong extension, or a trailing suffix that isn't a dotted version.
+        assert_eq!(matches_lib_name("libSDL2.solid", "SDL2", "lib", ".so"), false);
+        assert_eq!(matches_lib_name("libSDL2.so.", "SDL2", "lib", ".so"), false);
+        assert_eq!(matches_lib_name("libSDL2.so.1.", "SDL2", "lib", ".so"), false);
+    }
+
+    #[test]
+    fn is_version_suffix_test() {
+        use crate::is_version_suffix;
+        assert_eq!(is_version_suffix(".1.2.3"), true);
+        assert_eq!(is_version_suffix(".1"), true);
+        assert_eq!(is_version_suffix(""), false);
+        assert_eq!(is_version_suffix("."), false);
+        assert_eq!(is_version_suffix(".1."), false);
+        assert_eq!(is_version_suffix(".1.a"), false);
+        assert_eq!(is_version_suffix("id"), false);
+    }
+
+    #[test]
+    fn expand_target_template_test() {
+        use crate::expand_target_template;
+        let _guard = TARGET_ENV_LOCK.lock().unwrap();
+        std::env::set_var("TARGET", "x86_64-pc-windows-msvc");
+        std::env::set_var("CARGO_CFG_TARGET_OS", "windows");
+        std::env::set_var("CARGO_CFG_TARGET_ARCH", "x86_64");
+        assert_eq!(
+            expand_target_template("lib/{target}/{os}-{arch}.{dll_ext}").unwrap(),
+            "lib/x86_64-pc-windows-msvc/windows-x86_64.dll".to_string()
+        );
+    }
+
+    #[test]
+    fn install_dylibs_for_target_test() {
+        let cur_file = Path::new(file!());
+        let root = cur_file.parent().unwrap().parent().unwrap();
+        let out = root.join("target").join("test").join("install_dylibs_for_target");
+        let _ = fs::remove_dir_all(out.as_path());
+        fs::create_dir_all(out.as_path()).unwrap();
+        let data_dir = root.join("test_input");
+
+        use crate::*;
+        let _guard = TARGET_ENV_LOCK.lock().unwrap();
+        std::env::set_var("TARGET", "x86_64-pc-windows-msvc");
+        std::env::set_var("CARGO_CFG_TARGET_OS", "windows");
+        std::env::set_var("CARGO_CFG_TARGET_ARCH", "x86_64");
+        install_dylibs_for_target(data_dir.as_path(), "platform/{target}", None, Some(out.as_path())).unwrap();
+
+        assert_eq!(true, dir_list_equals(out.as_path(), vec![ "dummy0.dll", "dummy1.dll" ]));
+    }
 }
@@ -0,0 +1,73 @@
+#![cfg(feature = "tbz2")]
+
+use std::{
+    fs,
+    io,
+    path::Path,
+};
+
+pub(crate) fn extract_tar_bz2<T: AsRef<Path> + ?Sized>(
+    archive: &T,
+    target: &Path
+) -> Result<(), crate::ExtractError> {
+    use bzip2::read::BzDecoder;
+    let tar_bz2 = fs::File::open(archive)?;
+    let bz_decoder = BzDecoder::new(tar_bz2);
+    let mut archive = tar::Archive::new(bz_decoder);
+    archive.unpack(target).map_err(crate::ExtractError::Bz2Error)?;
+    Ok(())
+}
+
+/// Decompress a standalone (non-tar) `.bz2` file to a single file in `target`,
+/// named after `archive` with the `.bz2` suffix stripped.
+pub(crate) fn extract_bz2<T: AsRef<Path> + ?Sized>(
+    archive: &T,
+    target: &Path
+) -> Result<(), crate::ExtractError> {
+    use bzip2::read::BzDecoder;
+    let bz2 = fs::File::open(archive)?;
+    let mut decoder = BzDecoder::new(bz2);
+    fs::create_dir_all(target)?;
+    let out_name = archive.as_ref().file_name().unwrap().to_string_lossy();
+    let out_name = out_name.strip_suffix(".bz2").unwrap_or(&out_name);
+    let mut writer = fs::File::create(target.join(out_name))?;
+    io::copy(&mut decoder, &mut writer).map_err(crate::ExtractError::Bz2Error)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tbz2_tests {
+    #[test]
+    fn untarbz2() {
+        use crate::tests::dir_list_equals;
+        use crate::*;
+        let cur_file = Path::new(file!());
+        let root = cur_file.parent().unwrap().parent().unwrap();
+        let out = root.join("target").join("test").join("untarbz2");
+        let _ = fs::remove_dir_all(out.as_path());
+        let data_dir = root.join("test_input");
+        assert_eq!(extract_archive(data_dir.join("file.tar.bz2").as_path(), Some(out.as_path())).unwrap(), out);
+        assert_eq!(true, dir_list_equals(out.join("tbz2").as_path(), vec![ "compressed.txt" ]));
+        assert_eq!(
+            fs::read_to_string(out.join("tbz2").join("compressed.txt")).unwrap(),
+            "bz2 works too".to_string()
+        );
+    }
+
+    #[test]
+    fn unbz2() {
+        use crate::tests::dir_list_equals;
+        use crate::*;
+        let cur_file = Path::new(file!());
+        let root = cur_file.parent().unwrap().parent().unwrap();
+        let out = root.join("target").join("test").join("unbz2");
+        let _ = fs::remove_dir_all(out.as_path());
+        let data_dir = root.join("test_input");
+        assert_eq!(extract_archive(data_dir.join("sub.txt.bz2").as_path(), Some(out.as_path())).unwrap(), out);
+        assert_eq!(true, dir_list_equals(out.as_path(), vec![ "sub.txt" ]));
+        assert_eq!(
+            fs::read_to_string(out.join("sub.txt")).unwrap(),
+            "plain bz2 works too".to_string()
+        );
+    }
+}
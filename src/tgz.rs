@@ -1,7 +1,8 @@
-#![cfg(feature = "tar")]
+#![cfg(any(feature = "tar", feature = "tgz"))]
 
 use std::{
     fs,
+    io,
     path::Path,
 };
 
@@ -14,10 +15,29 @@ pub(crate) fn extract_tar_gz<T: AsRef<Path> + ?Sized>(
     let tar_gz = fs::File::open(archive)?;
     let gz_decoder = GzDecoder::new(tar_gz);
     let mut archive = tar::Archive::new(gz_decoder);
-    archive.unpack(target)?;
+    archive.unpack(target).map_err(crate::ExtractError::GzError)?;
+    Ok(())
+}
+
+/// Decompress a standalone (non-tar) `.gz` file to a single file in `target`,
+/// named after `archive` with the `.gz` suffix stripped.
+#[cfg(feature = "tgz")]
+pub(crate) fn extract_gz<T: AsRef<Path> + ?Sized>(
+    archive: &T,
+    target: &Path
+) -> Result<(), crate::ExtractError> {
+    use flate2::read::GzDecoder;
+    let gz = fs::File::open(archive)?;
+    let mut decoder = GzDecoder::new(gz);
+    fs::create_dir_all(target)?;
+    let out_name = archive.as_ref().file_name().unwrap().to_string_lossy();
+    let out_name = out_name.strip_suffix(".gz").unwrap_or(&out_name);
+    let mut writer = fs::File::create(target.join(out_name))?;
+    io::copy(&mut decoder, &mut writer).map_err(crate::ExtractError::GzError)?;
     Ok(())
 }
 
+#[cfg(feature = "tar")]
 pub(crate) fn extract_tar<T: AsRef<Path> + ?Sized>(
     archive: &T,
     target: &Path
@@ -47,4 +67,21 @@ mod tgz_tests {
             "this works as well".to_string()
         );
     }
+
+    #[test]
+    fn ungz() {
+        use crate::tests::dir_list_equals;
+        use crate::*;
+        let cur_file = Path::new(file!());
+        let root = cur_file.parent().unwrap().parent().unwrap();
+        let out = root.join("target").join("test").join("ungz");
+        let _ = fs::remove_dir_all(out.as_path());
+        let data_dir = root.join("test_input");
+        assert_eq!(extract_archive(data_dir.join("sub.txt.gz").as_path(), Some(out.as_path())).unwrap(), out);
+        assert_eq!(true, dir_list_equals(out.as_path(), vec![ "sub.txt" ]));
+        assert_eq!(
+            fs::read_to_string(out.join("sub.txt")).unwrap(),
+            "plain gz works too".to_string()
+        );
+    }
 }
\ No newline at end of file
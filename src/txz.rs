@@ -0,0 +1,73 @@
+#![cfg(feature = "txz")]
+
+use std::{
+    fs,
+    io,
+    path::Path,
+};
+
+pub(crate) fn extract_tar_xz<T: AsRef<Path> + ?Sized>(
+    archive: &T,
+    target: &Path
+) -> Result<(), crate::ExtractError> {
+    use xz2::read::XzDecoder;
+    let tar_xz = fs::File::open(archive)?;
+    let xz_decoder = XzDecoder::new(tar_xz);
+    let mut archive = tar::Archive::new(xz_decoder);
+    archive.unpack(target).map_err(crate::ExtractError::XzError)?;
+    Ok(())
+}
+
+/// Decompress a standalone (non-tar) `.xz` file to a single file in `target`,
+/// named after `archive` with the `.xz` suffix stripped.
+pub(crate) fn extract_xz<T: AsRef<Path> + ?Sized>(
+    archive: &T,
+    target: &Path
+) -> Result<(), crate::ExtractError> {
+    use xz2::read::XzDecoder;
+    let xz = fs::File::open(archive)?;
+    let mut decoder = XzDecoder::new(xz);
+    fs::create_dir_all(target)?;
+    let out_name = archive.as_ref().file_name().unwrap().to_string_lossy();
+    let out_name = out_name.strip_suffix(".xz").unwrap_or(&out_name);
+    let mut writer = fs::File::create(target.join(out_name))?;
+    io::copy(&mut decoder, &mut writer).map_err(crate::ExtractError::XzError)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod txz_tests {
+    #[test]
+    fn untarxz() {
+        use crate::tests::dir_list_equals;
+        use crate::*;
+        let cur_file = Path::new(file!());
+        let root = cur_file.parent().unwrap().parent().unwrap();
+        let out = root.join("target").join("test").join("untarxz");
+        let _ = fs::remove_dir_all(out.as_path());
+        let data_dir = root.join("test_input");
+        assert_eq!(extract_archive(data_dir.join("file.tar.xz").as_path(), Some(out.as_path())).unwrap(), out);
+        assert_eq!(true, dir_list_equals(out.join("txz").as_path(), vec![ "compressed.txt" ]));
+        assert_eq!(
+            fs::read_to_string(out.join("txz").join("compressed.txt")).unwrap(),
+            "xz works too".to_string()
+        );
+    }
+
+    #[test]
+    fn unxz() {
+        use crate::tests::dir_list_equals;
+        use crate::*;
+        let cur_file = Path::new(file!());
+        let root = cur_file.parent().unwrap().parent().unwrap();
+        let out = root.join("target").join("test").join("unxz");
+        let _ = fs::remove_dir_all(out.as_path());
+        let data_dir = root.join("test_input");
+        assert_eq!(extract_archive(data_dir.join("sub.txt.xz").as_path(), Some(out.as_path())).unwrap(), out);
+        assert_eq!(true, dir_list_equals(out.as_path(), vec![ "sub.txt" ]));
+        assert_eq!(
+            fs::read_to_string(out.join("sub.txt")).unwrap(),
+            "plain xz works too".to_string()
+        );
+    }
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "tzstd")]
+
+use std::{
+    fs,
+    io,
+    path::Path,
+};
+
+pub(crate) fn extract_tar_zst<T: AsRef<Path> + ?Sized>(
+    archive: &T,
+    target: &Path
+) -> Result<(), crate::ExtractError> {
+    let tar_zst = fs::File::open(archive)?;
+    let zst_decoder = zstd::Decoder::new(tar_zst).map_err(crate::ExtractError::ZstdError)?;
+    let mut archive = tar::Archive::new(zst_decoder);
+    archive.unpack(target).map_err(crate::ExtractError::ZstdError)?;
+    Ok(())
+}
+
+/// Decompress a standalone (non-tar) `.zst`/`.zstd` file to a single file in `target`,
+/// named after `archive` with the `.zst`/`.zstd` suffix stripped.
+pub(crate) fn extract_zst<T: AsRef<Path> + ?Sized>(
+    archive: &T,
+    target: &Path
+) -> Result<(), crate::ExtractError> {
+    let zst = fs::File::open(archive)?;
+    let mut decoder = zstd::Decoder::new(zst).map_err(crate::ExtractError::ZstdError)?;
+    fs::create_dir_all(target)?;
+    let out_name = archive.as_ref().file_name().unwrap().to_string_lossy();
+    let out_name = out_name
+        .strip_suffix(".zstd")
+        .or_else(|| out_name.strip_suffix(".zst"))
+        .unwrap_or(&out_name);
+    let mut writer = fs::File::create(target.join(out_name))?;
+    io::copy(&mut decoder, &mut writer).map_err(crate::ExtractError::ZstdError)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tzstd_tests {
+    #[test]
+    fn untarzst() {
+        use crate::tests::dir_list_equals;
+        use crate::*;
+        let cur_file = Path::new(file!());
+        let root = cur_file.parent().unwrap().parent().unwrap();
+        let out = root.join("target").join("test").join("untarzst");
+        let _ = fs::remove_dir_all(out.as_path());
+        let data_dir = root.join("test_input");
+        assert_eq!(extract_archive(data_dir.join("file.tar.zst").as_path(), Some(out.as_path())).unwrap(), out);
+        assert_eq!(true, dir_list_equals(out.join("tzstd").as_path(), vec![ "compressed.txt" ]));
+        assert_eq!(
+            fs::read_to_string(out.join("tzstd").join("compressed.txt")).unwrap(),
+            "zstd works too".to_string()
+        );
+    }
+
+    #[test]
+    fn unzst() {
+        use crate::tests::dir_list_equals;
+        use crate::*;
+        let cur_file = Path::new(file!());
+        let root = cur_file.parent().unwrap().parent().unwrap();
+        let out = root.join("target").join("test").join("unzst");
+        let _ = fs::remove_dir_all(out.as_path());
+        let data_dir = root.join("test_input");
+        assert_eq!(extract_archive(data_dir.join("sub.txt.zst").as_path(), Some(out.as_path())).unwrap(), out);
+        assert_eq!(true, dir_list_equals(out.as_path(), vec![ "sub.txt" ]));
+        assert_eq!(
+            fs::read_to_string(out.join("sub.txt")).unwrap(),
+            "plain zst works too".to_string()
+        );
+    }
+
+    #[test]
+    fn unzstd() {
+        use crate::tests::dir_list_equals;
+        use crate::*;
+        let cur_file = Path::new(file!());
+        let root = cur_file.parent().unwrap().parent().unwrap();
+        let out = root.join("target").join("test").join("unzstd");
+        let _ = fs::remove_dir_all(out.as_path());
+        let data_dir = root.join("test_input");
+        assert_eq!(extract_archive(data_dir.join("sub.txt.zstd").as_path(), Some(out.as_path())).unwrap(), out);
+        assert_eq!(true, dir_list_equals(out.as_path(), vec![ "sub.txt" ]));
+        assert_eq!(
+            fs::read_to_string(out.join("sub.txt")).unwrap(),
+            "plain zstd works too".to_string()
+        );
+    }
+}
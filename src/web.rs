@@ -0,0 +1,355 @@
+#![cfg(feature = "web")]
+
+use {
+    bytes::Buf,
+    std::{
+        env,
+        error,
+        fmt,
+        fs,
+        io,
+        path::{
+            Path,
+            PathBuf,
+        },
+    },
+    url::Url,
+};
+
+/// Get filename from URL
+fn url_fname(url: &Url) -> Option<&str> {
+    url
+        .path_segments()
+        .and_then(|segments| segments.last())
+        .and_then(|name| if name.is_empty() { None } else { Some(name) })
+}
+
+/// Hash `url` into a stable hex string suitable for use as a cache directory name.
+fn hash_url(url: &str) -> String {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = siphasher::sip::SipHasher13::new();
+    url.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// An expected digest of a downloaded file, used to verify its integrity before it's trusted.
+/// The digest is given as a lowercase hex string, the same format `sha256sum`/`b3sum` print.
+#[derive(Debug, Clone, Copy)]
+pub enum Checksum<'a> {
+    /// Expect the file to hash to this SHA-256 digest
+    Sha256(&'a str),
+    /// Expect the file to hash to this SHA-1 digest
+    Sha1(&'a str),
+    /// Expect the file to hash to this BLAKE3 digest
+    Blake3(&'a str),
+}
+
+impl Checksum<'_> {
+    /// The hex digest the caller expects.
+    fn expected(&self) -> &str {
+        use Checksum::*;
+        match *self {
+            Sha256(expected) | Sha1(expected) | Blake3(expected) => expected,
+        }
+    }
+
+    /// Hash `bytes` with this checksum's algorithm and return the lowercase hex digest.
+    fn digest(&self, bytes: &[u8]) -> String {
+        use Checksum::*;
+        match *self {
+            Sha256(_) => {
+                use sha2::{Digest, Sha256};
+                let mut hasher = Sha256::new();
+                hasher.update(bytes);
+                hex::encode(hasher.finalize())
+            },
+            Sha1(_) => {
+                use sha1::{Digest, Sha1};
+                let mut hasher = Sha1::new();
+                hasher.update(bytes);
+                hex::encode(hasher.finalize())
+            },
+            Blake3(_) => blake3::hash(bytes).to_hex().to_lowercase(),
+        }
+    }
+}
+
+/// Read `path` back and make sure it matches `checksum`, deleting it on mismatch so the next
+/// build retries the download instead of reusing a bad file forever.
+fn verify_checksum(path: &Path, checksum: Checksum<'_>) -> Result<(), DownloadError> {
+    let bytes = fs::read(path)?;
+    let actual = checksum.digest(&bytes);
+    let expected = checksum.expected().to_lowercase();
+    if actual == expected {
+        Ok(())
+    } else {
+        let _ = fs::remove_file(path);
+        Err(DownloadError::ChecksumMismatch { expected, actual })
+    }
+}
+
+/// A type specifying an error that occured during downloading a file
+#[derive(Debug)]
+pub enum DownloadError {
+    /// Get request failed
+    RequestError(reqwest::Error),
+    /// Invalid URL
+    ParseError(url::ParseError),
+    /// Couldn't deduce filename from the URL
+    NoFileNameError,
+    /// Couldn't save the fetched file
+    SaveError(io::Error),
+    /// The downloaded (or cached) file's digest didn't match the expected [Checksum]
+    ChecksumMismatch {
+        /// The digest the caller expected
+        expected: String,
+        /// The digest that was actually computed
+        actual: String,
+    },
+}
+
+impl From<url::ParseError> for DownloadError {
+    #[inline]
+    fn from(err: url::ParseError) -> DownloadError {
+        DownloadError::ParseError(err)
+    }
+}
+
+impl From<reqwest::Error> for DownloadError {
+    #[inline]
+    fn from(err: reqwest::Error) -> DownloadError {
+        DownloadError::RequestError(err)
+    }
+}
+
+impl From<io::Error> for DownloadError {
+    #[inline]
+    fn from(err: io::Error) -> DownloadError {
+        DownloadError::SaveError(err)
+    }
+}
+
+impl fmt::Display for DownloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use DownloadError::*;
+        match *self {
+            NoFileNameError => write!(f, "couldn't infer file name from the url"),
+            RequestError(ref e) => e.fmt(f),
+            ParseError(ref e) => e.fmt(f),
+            SaveError(ref e) => e.fmt(f),
+            ChecksumMismatch { ref expected, ref actual } => write!(
+                f,
+                "checksum mismatch: expected {}, got {}",
+                expected, actual
+            ),
+        }
+    }
+}
+
+impl error::Error for DownloadError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        use DownloadError::*;
+        match *self {
+            NoFileNameError => None,
+            RequestError(ref e) => Some(e),
+            ParseError(ref e) => Some(e),
+            SaveError(ref e) => Some(e),
+            ChecksumMismatch { .. } => None,
+        }
+    }
+}
+
+/// Download file if it doesn't already exist, and return the file's location.
+///
+/// You can use `out_dir` to specify the download directory, otherwise the build script output directory will be used.
+///
+/// If `checksum` is provided, the file's digest is checked (both right after downloading it and
+/// when a cached copy is reused) and [DownloadError::ChecksumMismatch] is returned on a mismatch,
+/// with the bad file deleted so the next call retries the download.
+///
+/// Example:
+/// ```
+/// # fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// librarian::download_or_find_file("https://example.com/file.zip", None, None)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn download_or_find_file(
+    url: &str,
+    out_dir: Option<&Path>,
+    checksum: Option<Checksum<'_>>
+) -> Result<PathBuf, DownloadError> {
+    use DownloadError::*;
+
+    let url_parsed = Url::parse(url)?;
+    let fname = url_fname(&url_parsed);
+    if let Some(fname) = fname {
+        let out_dir = if let Some(out_dir) = out_dir {
+            PathBuf::from(out_dir)
+        } else {
+            let out_dir = env::var("OUT_DIR").expect("You must provide the output directory when not running from a build script.");
+            PathBuf::from(out_dir)
+        };
+        let path = out_dir.join(fname);
+        if !path.exists() {
+            let response = reqwest::blocking::get(url)?;
+            let mut dest = fs::File::create(path.clone())?;
+            let content = response.bytes()?;
+            let mut bytes = content.bytes();
+            io::copy(&mut bytes, &mut dest)?;
+        }
+        if let Some(checksum) = checksum {
+            verify_checksum(&path, checksum)?;
+        }
+        Ok(path)
+    } else {
+        Err(NoFileNameError)
+    }
+}
+
+/// Like [download_or_find_file], but keys the cache by a hash of the full URL rather than the
+/// URL's last path segment, so two different URLs that happen to end in the same filename never
+/// collide, and a changed upstream URL is never served a stale file cached under the old one.
+///
+/// The file is stored at `cache_root/<hash of url>/<filename>`, so repeated build-script runs
+/// across projects that share `cache_root` can dedupe the same artifact.
+///
+/// Example:
+/// ```
+/// # fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// # let cache_root = std::path::Path::new(".");
+/// librarian::download_or_find_file_cached("https://example.com/file.zip", cache_root, None)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn download_or_find_file_cached(
+    url: &str,
+    cache_root: &Path,
+    checksum: Option<Checksum<'_>>
+) -> Result<PathBuf, DownloadError> {
+    let cache_dir = cache_root.join(hash_url(url));
+    fs::create_dir_all(&cache_dir)?;
+    download_or_find_file(url, Some(cache_dir.as_path()), checksum)
+}
+
+/// Download a platform-specific artifact, substituting `{target}`, `{os}`, `{arch}`, and
+/// `{dll_ext}` placeholders in `url_template` for the current build target before fetching it.
+/// Pair this with [install_dylibs_for_target](crate::install_dylibs_for_target) to install only
+/// the binaries matching the current platform out of a multi-platform archive. Only works from
+/// build scripts.
+///
+/// If `checksum` is provided, the downloaded (or cached) file's digest is checked the same way
+/// as in [download_or_find_file].
+///
+/// Example:
+/// ```
+/// # fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// # let out_dir = std::path::Path::new(".");
+/// librarian::download_for_target("https://example.com/lib-{target}.zip", out_dir, None)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn download_for_target(
+    url_template: &str,
+    out_dir: &Path,
+    checksum: Option<Checksum<'_>>
+) -> Result<PathBuf, DownloadError> {
+    let url = crate::expand_target_template(url_template)
+        .expect("Couldn't resolve target placeholders (TARGET/CARGO_CFG_TARGET_OS/CARGO_CFG_TARGET_ARCH); are you running from a build script?");
+    download_or_find_file(&url, Some(out_dir), checksum)
+}
+
+#[cfg(test)]
+mod download_tests {
+    #[test]
+    fn unzip() {
+        use crate::tests::dir_list_equals;
+        use crate::*;
+        let cur_file = Path::new(file!());
+        let root = cur_file.parent().unwrap().parent().unwrap();
+        let out = root.join("target").join("test").join("unzip");
+        let _ = fs::remove_dir_all(out.as_path());
+        let data_dir = root.join("test_input");
+        assert_eq!(extract_archive(data_dir.join("file.zip").as_path(), Some(out.as_path())).unwrap(), out);
+        assert_eq!(true, dir_list_equals(out.join("zip").as_path(), vec![ "compressed.txt" ]));
+        assert_eq!(
+            fs::read_to_string(out.join("zip").join("compressed.txt")).unwrap(),
+            "it works!".to_string()
+        );
+    }
+
+    #[test]
+    fn web() {
+        use crate::tests::dir_list_equals;
+        use crate::*;
+        let cur_file = Path::new(file!());
+        let root = cur_file.parent().unwrap().parent().unwrap();
+        let out = root.join("target").join("test").join("web");
+        let _ = fs::remove_dir_all(out.as_path());
+        let _ = fs::create_dir_all(out.as_path());
+        let url = "https://httpbin.org/base64/YWJj";
+        let out_expect = out.join("YWJj");
+        assert_eq!(download_or_find_file(url, Some(out.as_path()), None).unwrap(), out_expect);
+        assert_eq!(true, dir_list_equals(out.as_path(), vec![ "YWJj" ]));
+        assert_eq!(
+            fs::read_to_string(out_expect.as_path()).unwrap(),
+            "abc".to_string()
+        );
+        let url = "http://invalid.url/but/the/file/is/still/cached/YWJj";
+        assert_eq!(download_or_find_file(url, Some(out.as_path()), None).unwrap(), out_expect);
+    }
+
+    #[test]
+    fn checksum_mismatch() {
+        use crate::*;
+        let cur_file = Path::new(file!());
+        let root = cur_file.parent().unwrap().parent().unwrap();
+        let out = root.join("target").join("test").join("checksum_mismatch");
+        let _ = fs::remove_dir_all(out.as_path());
+        let _ = fs::create_dir_all(out.as_path());
+        let url = "https://httpbin.org/base64/YWJj";
+        let err = download_or_find_file(url, Some(out.as_path()), Some(Checksum::Sha256("0000000000000000000000000000000000000000000000000000000000000000"))).unwrap_err();
+        assert!(matches!(err, DownloadError::ChecksumMismatch { .. }));
+        assert!(!out.join("YWJj").exists());
+    }
+
+    #[test]
+    fn cached_download() {
+        use crate::*;
+        let cur_file = Path::new(file!());
+        let root = cur_file.parent().unwrap().parent().unwrap();
+        let out = root.join("target").join("test").join("cached_download");
+        let _ = fs::remove_dir_all(out.as_path());
+        let _ = fs::create_dir_all(out.as_path());
+        let url = "https://httpbin.org/base64/YWJj";
+        let path = download_or_find_file_cached(url, out.as_path(), None).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "abc".to_string());
+        // A different URL that happens to end in the same filename must get its own cache
+        // entry instead of silently reusing the first URL's cached file.
+        let other_url = "http://invalid.url/but/the/file/is/still/YWJj";
+        assert!(download_or_find_file_cached(other_url, out.as_path(), None).is_err());
+    }
+
+    #[test]
+    fn download_for_target_test() {
+        use crate::tests::dir_list_equals;
+        use crate::*;
+        let cur_file = Path::new(file!());
+        let root = cur_file.parent().unwrap().parent().unwrap();
+        let out = root.join("target").join("test").join("download_for_target");
+        let _ = fs::remove_dir_all(out.as_path());
+        let _ = fs::create_dir_all(out.as_path());
+
+        let _guard = crate::tests::TARGET_ENV_LOCK.lock().unwrap();
+        std::env::set_var("TARGET", "x86_64-unknown-linux-gnu");
+        std::env::set_var("CARGO_CFG_TARGET_OS", "linux");
+        std::env::set_var("CARGO_CFG_TARGET_ARCH", "YWJj");
+        let out_expect = out.join("YWJj");
+        assert_eq!(download_for_target("https://httpbin.org/base64/{arch}", out.as_path(), None).unwrap(), out_expect);
+        assert_eq!(true, dir_list_equals(out.as_path(), vec![ "YWJj" ]));
+        assert_eq!(
+            fs::read_to_string(out_expect.as_path()).unwrap(),
+            "abc".to_string()
+        );
+    }
+}
\ No newline at end of file
@@ -1,14 +1,14 @@
 #![cfg(feature = "zip")]
 
 use {
-    crate::ExtractError,
+    crate::{reject_unsafe_path, ExtractError},
     rc_zip::{
         prelude::*,
         EntryContents,
     },
     std::{
         fs,
-        io,
+        io::{self, Read},
         path::Path,
     },
 };
@@ -20,6 +20,22 @@ impl From<rc_zip::Error> for ExtractError {
     }
 }
 
+/// Apply the Unix mode bits stored in a zip entry (if any) to the file at `path`.
+/// No-op on non-unix targets, since the permission bits don't mean anything there.
+#[cfg(unix)]
+fn set_unix_mode(path: &Path, mode: Option<u32>) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    if let Some(mode) = mode {
+        fs::set_permissions(path, fs::Permissions::from_mode(mode))?;
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_unix_mode(_path: &Path, _mode: Option<u32>) -> io::Result<()> {
+    Ok(())
+}
+
 pub(crate) fn extract_zip<T: AsRef<Path> + ?Sized>(
     archive: &T,
     target: &Path
@@ -29,20 +45,34 @@ pub(crate) fn extract_zip<T: AsRef<Path> + ?Sized>(
     for entry in reader.entries() {
         match entry.contents() {
             EntryContents::Directory(c) => {
+                reject_unsafe_path(c.entry.name())?;
                 let path = target.join(c.entry.name());
                 fs::create_dir_all(path.parent().unwrap())?;
             },
             EntryContents::File(c) => {
+                reject_unsafe_path(c.entry.name())?;
                 let path = target.join(c.entry.name());
                 fs::create_dir_all(path.parent().unwrap())?;
-                let mut writer = fs::File::create(path)?;
+                let mut writer = fs::File::create(&path)?;
                 let mut reader = c
                     .entry
                     .reader(|offset| positioned_io::Cursor::new_pos(&zipfile, offset));
 
                 io::copy(&mut reader, &mut writer)?;
+                set_unix_mode(&path, c.entry.unix_mode())?;
+            },
+            EntryContents::Symlink(c) => {
+                reject_unsafe_path(c.entry.name())?;
+                let path = target.join(c.entry.name());
+                fs::create_dir_all(path.parent().unwrap())?;
+                let mut link_target = String::new();
+                c.entry
+                    .reader(|offset| positioned_io::Cursor::new_pos(&zipfile, offset))
+                    .read_to_string(&mut link_target)?;
+                reject_unsafe_path(&link_target)?;
+                #[cfg(unix)]
+                std::os::unix::fs::symlink(link_target, &path)?;
             },
-            // Symlinks aren't supported! Open an issue if you need them.
             _ => {}
         }
     }
@@ -67,4 +97,27 @@ mod zip_tests {
             "it works!".to_string()
         );
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn unzip_permissions_and_symlinks() {
+        use crate::tests::dir_list_equals;
+        use crate::*;
+        use std::os::unix::fs::PermissionsExt;
+        let cur_file = Path::new(file!());
+        let root = cur_file.parent().unwrap().parent().unwrap();
+        let out = root.join("target").join("test").join("unzip_unix");
+        let _ = fs::remove_dir_all(out.as_path());
+        let data_dir = root.join("test_input");
+        assert_eq!(extract_archive(data_dir.join("file_unix.zip").as_path(), Some(out.as_path())).unwrap(), out);
+        assert_eq!(true, dir_list_equals(out.join("zip_unix").as_path(), vec![ "run.sh", "link.sh" ]));
+
+        let exe = out.join("zip_unix").join("run.sh");
+        let mode = fs::metadata(&exe).unwrap().permissions().mode();
+        assert_eq!(mode & 0o111, 0o111);
+
+        let link = out.join("zip_unix").join("link.sh");
+        assert_eq!(fs::symlink_metadata(&link).unwrap().file_type().is_symlink(), true);
+        assert_eq!(fs::read_link(&link).unwrap(), Path::new("run.sh"));
+    }
 }
\ No newline at end of file